@@ -5,7 +5,11 @@ use io_extras::read_write::{ReadHalf, WriteHalf};
 #[cfg(windows)]
 use is_terminal::IsTerminal;
 #[cfg(windows)]
-use {io_extras::os::windows::AsHandleOrSocket, std::os::windows::io::AsRawHandle};
+use {
+    io_extras::os::windows::AsHandleOrSocket,
+    std::os::windows::io::AsRawHandle,
+    windows_sys::Win32::Storage::FileSystem::{GetFileType, FILE_TYPE_CHAR},
+};
 
 #[derive(Default, Debug)]
 pub(crate) struct ReadConfig {
@@ -93,6 +97,35 @@ pub(crate) fn detect_read_config<Grip: AsGrip>(handle: &Grip) -> Option<ReadConf
     }
 }
 
+#[cfg(not(windows))]
+pub(crate) fn detect_null_sink<Grip: AsGrip>(handle: &Grip) -> bool {
+    use rustix::fs::{fstat, stat, FileType};
+
+    // Compare device numbers rather than paths, so that this works however
+    // the handle was opened.
+    match (fstat(handle), stat("/dev/null")) {
+        (Ok(handle_stat), Ok(null_stat)) => {
+            FileType::from_raw_mode(handle_stat.st_mode) == FileType::CharacterDevice
+                && handle_stat.st_rdev == null_stat.st_rdev
+        }
+        _ => false,
+    }
+}
+
+#[cfg(windows)]
+pub(crate) fn detect_null_sink<Grip: AsGrip>(grip: &Grip) -> bool {
+    match grip.as_grip().as_handle_or_socket().as_handle() {
+        // `NUL` doesn't support name queries, so identify it as a character
+        // device which isn't a console. This also matches other character
+        // devices, such as serial ports and printers.
+        Some(handle) if !handle.is_terminal() => {
+            let file_type = unsafe { GetFileType(handle.as_raw_handle() as _) };
+            file_type == FILE_TYPE_CHAR
+        }
+        _ => false,
+    }
+}
+
 #[cfg(not(windows))]
 pub(crate) fn detect_write_config<Grip: AsGrip>(handle: &Grip) -> Option<WriteConfig> {
     if rustix::termios::isatty(handle) {
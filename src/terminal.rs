@@ -1,5 +1,5 @@
 use duplex::Duplex;
-use std::io::{Read, Write};
+use std::io::{self, Read, Write};
 
 /// A trait for devices which may be connected to terminals.
 pub trait Terminal {}
@@ -35,6 +35,83 @@ pub trait WriteTerminal: Write + Terminal {
     ///
     /// Also known as `isatty`.
     fn is_output_terminal(&self) -> bool;
+
+    /// Test whether the output is known to be discarded, such as when it's
+    /// redirected to `/dev/null` on Unix or `NUL` on Windows. Applications
+    /// may use this to skip expensive rendering entirely.
+    ///
+    /// On Windows, `NUL` can't be distinguished from other non-console
+    /// character devices, such as serial ports and printers, so this also
+    /// returns `true` for those.
+    fn is_null_sink(&self) -> bool {
+        false
+    }
+
+    /// Redefine palette entry `index` to the given RGB color, using the OSC 4
+    /// control sequence. Does nothing if the output isn't a terminal.
+    ///
+    /// OSC 4 defines a 256-entry palette, so every `index` from 0 through 255
+    /// is valid, regardless of [`color_support`](Self::color_support).
+    fn set_palette_color(&mut self, index: u8, r: u8, g: u8, b: u8) -> io::Result<()> {
+        if !self.is_output_terminal() {
+            return Ok(());
+        }
+        write!(
+            self,
+            "\x1b]4;{};rgb:{:02x}/{:02x}/{:02x}\x1b\\",
+            index, r, g, b
+        )
+    }
+
+    /// Reset all palette entries to their defaults, using the OSC 104 control
+    /// sequence. Does nothing if the output isn't a terminal.
+    fn reset_palette(&mut self) -> io::Result<()> {
+        if !self.is_output_terminal() {
+            return Ok(());
+        }
+        self.write_all(b"\x1b]104\x1b\\")
+    }
+
+    /// Mark the start of a shell prompt, using the OSC 133 `A` control
+    /// sequence. Does nothing if the output isn't a terminal.
+    fn mark_prompt_start(&mut self) -> io::Result<()> {
+        if !self.is_output_terminal() {
+            return Ok(());
+        }
+        self.write_all(b"\x1b]133;A\x1b\\")
+    }
+
+    /// Mark the end of a shell prompt and the start of the user's command
+    /// input, using the OSC 133 `B` control sequence. Does nothing if the
+    /// output isn't a terminal.
+    fn mark_prompt_end(&mut self) -> io::Result<()> {
+        if !self.is_output_terminal() {
+            return Ok(());
+        }
+        self.write_all(b"\x1b]133;B\x1b\\")
+    }
+
+    /// Mark the start of a command's output, using the OSC 133 `C` control
+    /// sequence. Does nothing if the output isn't a terminal.
+    fn mark_command_start(&mut self) -> io::Result<()> {
+        if !self.is_output_terminal() {
+            return Ok(());
+        }
+        self.write_all(b"\x1b]133;C\x1b\\")
+    }
+
+    /// Mark the end of a command's output, using the OSC 133 `D` control
+    /// sequence, optionally reporting the command's exit code. Does nothing
+    /// if the output isn't a terminal.
+    fn mark_command_end(&mut self, exit_code: Option<i32>) -> io::Result<()> {
+        if !self.is_output_terminal() {
+            return Ok(());
+        }
+        match exit_code {
+            Some(exit_code) => write!(self, "\x1b]133;D;{}\x1b\\", exit_code),
+            None => self.write_all(b"\x1b]133;D\x1b\\"),
+        }
+    }
 }
 
 /// An extension trait for input/output streams connected to terminals.
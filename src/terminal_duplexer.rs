@@ -1,6 +1,6 @@
 //! The `TerminalDuplex` struct.
 
-use crate::config::{detect_read_write_config, ReadConfig, WriteConfig};
+use crate::config::{detect_null_sink, detect_read_write_config, ReadConfig, WriteConfig};
 use crate::{DuplexTerminal, ReadTerminal, Terminal, TerminalColorSupport, WriteTerminal};
 use duplex::{Duplex, HalfDuplex};
 use io_extras::grip::AsReadWriteGrip;
@@ -9,6 +9,7 @@ use io_extras::os::windows::{
     AsRawReadWriteHandleOrSocket, AsReadWriteHandleOrSocket, BorrowedHandleOrSocket,
     RawHandleOrSocket,
 };
+use io_extras::read_write::WriteHalf;
 use std::fmt;
 use std::io::{self, IoSlice, IoSliceMut, Read, Write};
 #[cfg(not(windows))]
@@ -23,6 +24,7 @@ pub struct TerminalDuplexer<Inner: Duplex> {
     inner: Inner,
     read_config: Option<ReadConfig>,
     write_config: Option<WriteConfig>,
+    null_sink: bool,
 }
 
 impl<Inner: Duplex + AsReadWriteGrip> TerminalDuplexer<Inner> {
@@ -30,10 +32,13 @@ impl<Inner: Duplex + AsReadWriteGrip> TerminalDuplexer<Inner> {
     /// terminal properties using its `AsGrip` implementation.
     pub fn with_handle(inner: Inner) -> Self {
         let (read_config, write_config) = detect_read_write_config(&inner);
+        // Only non-terminals can be null sinks, so skip the check otherwise.
+        let null_sink = write_config.is_none() && detect_null_sink(&WriteHalf::new(&inner));
         Self {
             inner,
             read_config,
             write_config,
+            null_sink,
         }
     }
 }
@@ -46,6 +51,7 @@ impl<Inner: Duplex + Read + Write> TerminalDuplexer<Inner> {
             inner,
             read_config: None,
             write_config: None,
+            null_sink: false,
         }
     }
 
@@ -122,7 +128,7 @@ impl<Inner: Duplex> Terminal for TerminalDuplexer<Inner> {}
 
 impl<Inner: Duplex + Read + Write> ReadTerminal for TerminalDuplexer<Inner> {
     fn is_line_by_line(&self) -> bool {
-        self.read_config.as_ref().map_or(false, |c| c.line_by_line)
+        self.read_config.as_ref().is_some_and(|c| c.line_by_line)
     }
 
     fn is_input_terminal(&self) -> bool {
@@ -140,12 +146,16 @@ impl<Inner: Duplex + Read + Write> WriteTerminal for TerminalDuplexer<Inner> {
     fn color_preference(&self) -> bool {
         self.write_config
             .as_ref()
-            .map_or(false, |c| c.color_preference)
+            .is_some_and(|c| c.color_preference)
     }
 
     fn is_output_terminal(&self) -> bool {
         self.write_config.is_some()
     }
+
+    fn is_null_sink(&self) -> bool {
+        self.null_sink
+    }
 }
 
 impl<Inner: Duplex + HalfDuplex> DuplexTerminal for TerminalDuplexer<Inner> {}
@@ -7,7 +7,10 @@ use io_extras::grip::AsGrip;
 use io_extras::os::windows::{
     AsHandleOrSocket, AsRawHandleOrSocket, BorrowedHandleOrSocket, RawHandleOrSocket,
 };
-use std::io::{self, IoSliceMut, Read};
+use std::fmt;
+use std::io::{self, IoSliceMut, Read, Write};
+use std::str;
+use std::sync::Mutex;
 #[cfg(not(windows))]
 use {
     io_extras::os::rustix::{AsRawFd, RawFd},
@@ -15,10 +18,14 @@ use {
 };
 
 /// A wrapper around a `Read` which adds minimal terminal support.
-#[derive(Debug)]
 pub struct TerminalReader<Inner: Read> {
     inner: Inner,
     read_config: Option<ReadConfig>,
+    // The `Mutex` is never locked, since the sink is only used through
+    // `&mut self`; it just keeps `TerminalReader` `Sync`.
+    debug_log: Option<Mutex<Box<dyn Write + Send>>>,
+    normalize_enter: bool,
+    pending_cr: bool,
 }
 
 impl<Inner: Read + AsGrip> TerminalReader<Inner> {
@@ -27,7 +34,13 @@ impl<Inner: Read + AsGrip> TerminalReader<Inner> {
     #[inline]
     pub fn with_handle(inner: Inner) -> Self {
         let read_config = detect_read_config(&inner);
-        Self { inner, read_config }
+        Self {
+            inner,
+            read_config,
+            debug_log: None,
+            normalize_enter: false,
+            pending_cr: false,
+        }
     }
 }
 
@@ -39,14 +52,89 @@ impl<Inner: Read> TerminalReader<Inner> {
         Self {
             inner,
             read_config: None,
+            debug_log: None,
+            normalize_enter: false,
+            pending_cr: false,
         }
     }
 
+    /// Copy all bytes read from the stream into `sink`, in the order they're
+    /// read, while passing them through unchanged. This is useful for
+    /// debugging input encoding issues. Errors writing to `sink` are ignored.
+    #[inline]
+    pub fn debug_log_input(mut self, sink: impl Write + Send + 'static) -> Self {
+        self.debug_log = Some(Mutex::new(Box::new(sink)));
+        self
+    }
+
+    /// Translate `\r` and `\r\n` into `\n` on input, so that line
+    /// endings are uniform whether the terminal is in raw mode, which sends
+    /// `\r` for Enter, or canonical mode, which sends `\n`. A `\r\n`
+    /// split across two reads produces a single `\n`.
+    #[inline]
+    pub fn normalize_enter(mut self, enable: bool) -> Self {
+        self.normalize_enter = enable;
+        self
+    }
+
     /// Consume `self` and return the inner stream.
     #[inline]
     pub fn into_inner(self) -> Inner {
         self.inner
     }
+
+    /// Apply `normalize_enter` translation to `buf` in place, returning the
+    /// new length.
+    fn normalize(&mut self, buf: &mut [u8]) -> usize {
+        if buf.is_empty() {
+            return 0;
+        }
+
+        // Drop a leading `\n` completing a `\r\n` from the previous read.
+        let skip = usize::from(self.pending_cr && buf[0] == b'\n');
+        let mut len = 0;
+        let mut prev_cr = false;
+        for i in skip..buf.len() {
+            let byte = buf[i];
+            if byte == b'\n' && prev_cr {
+                prev_cr = false;
+                continue;
+            }
+            prev_cr = byte == b'\r';
+            buf[len] = if prev_cr { b'\n' } else { byte };
+            len += 1;
+        }
+        self.pending_cr = prev_cr;
+        len
+    }
+
+    fn log_input(&mut self, bytes: &[u8]) {
+        if let Some(Ok(debug_log)) = self.debug_log.as_mut().map(Mutex::get_mut) {
+            debug_log.write_all(bytes).ok();
+        }
+    }
+
+    fn log_input_vectored(&mut self, bufs: &[IoSliceMut], mut n: usize) {
+        for buf in bufs {
+            if n == 0 {
+                break;
+            }
+            let len = n.min(buf.len());
+            self.log_input(&buf[..len]);
+            n -= len;
+        }
+    }
+}
+
+impl<Inner: Read + fmt::Debug> fmt::Debug for TerminalReader<Inner> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TerminalReader")
+            .field("inner", &self.inner)
+            .field("read_config", &self.read_config)
+            .field("debug_log", &self.debug_log.is_some())
+            .field("normalize_enter", &self.normalize_enter)
+            .finish()
+    }
 }
 
 #[cfg(not(windows))]
@@ -86,7 +174,7 @@ impl<Inner: Read> Terminal for TerminalReader<Inner> {}
 impl<Inner: Read> ReadTerminal for TerminalReader<Inner> {
     #[inline]
     fn is_line_by_line(&self) -> bool {
-        self.read_config.as_ref().map_or(false, |c| c.line_by_line)
+        self.read_config.as_ref().is_some_and(|c| c.line_by_line)
     }
 
     #[inline]
@@ -98,12 +186,32 @@ impl<Inner: Read> ReadTerminal for TerminalReader<Inner> {
 impl<Inner: Read> Read for TerminalReader<Inner> {
     #[inline]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.read(buf)
+        loop {
+            let n = self.inner.read(buf)?;
+            self.log_input(&buf[..n]);
+            if !self.normalize_enter {
+                return Ok(n);
+            }
+            // If the only byte was a dropped `\n`, read again rather than
+            // returning 0, which would look like end of stream.
+            let len = self.normalize(&mut buf[..n]);
+            if len != 0 || n == 0 {
+                return Ok(len);
+            }
+        }
     }
 
     #[inline]
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
-        self.inner.read_vectored(bufs)
+        if self.normalize_enter {
+            return match bufs.iter_mut().find(|b| !b.is_empty()) {
+                Some(buf) => self.read(buf),
+                None => Ok(0),
+            };
+        }
+        let n = self.inner.read_vectored(bufs)?;
+        self.log_input_vectored(bufs, n);
+        Ok(n)
     }
 
     #[cfg(can_vector)]
@@ -114,16 +222,70 @@ impl<Inner: Read> Read for TerminalReader<Inner> {
 
     #[inline]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
-        self.inner.read_to_end(buf)
+        let start = buf.len();
+        let result = self.inner.read_to_end(buf);
+        self.log_input(&buf[start..]);
+        if !self.normalize_enter {
+            return result;
+        }
+        // Normalize whatever was read, even if an error occurred partway.
+        let len = self.normalize(&mut buf[start..]);
+        buf.truncate(start + len);
+        result.map(|_| len)
     }
 
     #[inline]
     fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
-        self.inner.read_to_string(buf)
+        if self.debug_log.is_none() && !self.normalize_enter {
+            return self.inner.read_to_string(buf);
+        }
+        // Read bytes first, so that they're logged even if an error occurs or
+        // they aren't valid UTF-8.
+        let mut bytes = Vec::new();
+        let result = self.read_to_end(&mut bytes);
+        match (result, str::from_utf8(&bytes)) {
+            (Ok(_), Ok(s)) => {
+                buf.push_str(s);
+                Ok(s.len())
+            }
+            (Ok(_), Err(_)) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )),
+            // Report I/O errors first, keeping any valid UTF-8 prefix, since
+            // the error may have cut a multibyte sequence short.
+            (Err(e), Ok(s)) => {
+                buf.push_str(s);
+                Err(e)
+            }
+            (Err(e), Err(utf8_error)) => {
+                let valid = &bytes[..utf8_error.valid_up_to()];
+                buf.push_str(str::from_utf8(valid).unwrap());
+                Err(e)
+            }
+        }
     }
 
     #[inline]
-    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
-        self.inner.read_exact(buf)
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> io::Result<()> {
+        if self.debug_log.is_none() && !self.normalize_enter {
+            return self.inner.read_exact(buf);
+        }
+        // Read incrementally, so that bytes are logged even if an error
+        // occurs partway through.
+        while !buf.is_empty() {
+            match self.read(buf) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "failed to fill whole buffer",
+                    ))
+                }
+                Ok(n) => buf = &mut buf[n..],
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
     }
 }
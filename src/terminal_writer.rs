@@ -1,6 +1,6 @@
 //! The `TerminalWriter` struct.
 
-use crate::config::{detect_write_config, WriteConfig};
+use crate::config::{detect_null_sink, detect_write_config, WriteConfig};
 use crate::{Terminal, TerminalColorSupport, WriteTerminal};
 use io_extras::grip::AsGrip;
 #[cfg(windows)]
@@ -20,6 +20,7 @@ use {
 pub struct TerminalWriter<Inner: Write> {
     inner: Inner,
     write_config: Option<WriteConfig>,
+    null_sink: bool,
 }
 
 impl<Inner: Write + AsGrip> TerminalWriter<Inner> {
@@ -27,12 +28,17 @@ impl<Inner: Write + AsGrip> TerminalWriter<Inner> {
     /// terminal properties using its `AsGrip` implementation.
     pub fn with_handle(inner: Inner) -> Self {
         let write_config = detect_write_config(&inner);
+        // Only non-terminals can be null sinks, so skip the check otherwise.
+        let null_sink = write_config.is_none() && detect_null_sink(&inner);
         Self {
             inner,
             write_config,
+            null_sink,
         }
     }
+}
 
+impl<Inner: Write> TerminalWriter<Inner> {
     /// Wrap a `TerminalWriter` around the given stream, using the given
     /// terminal properties.
     pub fn from(
@@ -51,17 +57,17 @@ impl<Inner: Write + AsGrip> TerminalWriter<Inner> {
             } else {
                 None
             },
+            null_sink: false,
         }
     }
-}
 
-impl<Inner: Write> TerminalWriter<Inner> {
     /// Wrap a `TerminalWriter` around the given stream, using
     /// conservative terminal properties.
     pub fn generic(inner: Inner) -> Self {
         Self {
             inner,
             write_config: None,
+            null_sink: false,
         }
     }
 
@@ -83,7 +89,7 @@ impl<Inner: Write + AsRawFd> AsRawFd for TerminalWriter<Inner> {
 #[cfg(not(windows))]
 impl<Inner: Write + AsFd> AsFd for TerminalWriter<Inner> {
     #[inline]
-    fn as_fd(&self) -> BorrowedFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
         self.inner.as_fd()
     }
 }
@@ -116,12 +122,16 @@ impl<Inner: Write> WriteTerminal for TerminalWriter<Inner> {
     fn color_preference(&self) -> bool {
         self.write_config
             .as_ref()
-            .map_or(false, |c| c.color_preference)
+            .is_some_and(|c| c.color_preference)
     }
 
     fn is_output_terminal(&self) -> bool {
         self.write_config.is_some()
     }
+
+    fn is_null_sink(&self) -> bool {
+        self.null_sink
+    }
 }
 
 impl<Inner: Write> Write for TerminalWriter<Inner> {
@@ -0,0 +1,237 @@
+use std::io::{self, IoSliceMut, Read, Write};
+use std::sync::{Arc, Mutex};
+use terminal_io::TerminalReader;
+
+/// A `Read` which returns the given chunks one per `read` call, followed by
+/// an optional error.
+struct Chunks {
+    chunks: Vec<&'static [u8]>,
+    error: Option<io::ErrorKind>,
+}
+
+impl Chunks {
+    fn new(chunks: &[&'static [u8]]) -> Self {
+        Self {
+            chunks: chunks.to_vec(),
+            error: None,
+        }
+    }
+
+    fn then_fail(mut self, kind: io::ErrorKind) -> Self {
+        self.error = Some(kind);
+        self
+    }
+}
+
+impl Read for Chunks {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.chunks.is_empty() {
+            return match self.error.take() {
+                Some(kind) => Err(kind.into()),
+                None => Ok(0),
+            };
+        }
+        let chunk = self.chunks.remove(0);
+        let n = chunk.len().min(buf.len());
+        buf[..n].copy_from_slice(&chunk[..n]);
+        if n < chunk.len() {
+            self.chunks.insert(0, &chunk[n..]);
+        }
+        Ok(n)
+    }
+}
+
+/// A `Write` which appends to a shared buffer.
+#[derive(Clone, Default)]
+struct Sink(Arc<Mutex<Vec<u8>>>);
+
+impl Sink {
+    fn contents(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn debug_log_input_read() {
+    let sink = Sink::default();
+    let mut reader = TerminalReader::generic(Chunks::new(&[b"ab", b"\x1b[A", b"c"]))
+        .debug_log_input(sink.clone());
+
+    assert_eq!(read_all(&mut reader), b"ab\x1b[Ac");
+    assert_eq!(sink.contents(), b"ab\x1b[Ac");
+}
+
+#[test]
+fn debug_log_input_read_vectored() {
+    let sink = Sink::default();
+    let mut reader = TerminalReader::generic(&b"hello, world"[..]).debug_log_input(sink.clone());
+
+    let mut first = [0_u8; 5];
+    let mut second = [0_u8; 16];
+    let n = reader
+        .read_vectored(&mut [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)])
+        .unwrap();
+
+    assert_eq!(n, 12);
+    assert_eq!(&first, b"hello");
+    assert_eq!(&second[..7], b", world");
+    assert_eq!(sink.contents(), b"hello, world");
+}
+
+#[test]
+fn debug_log_input_error() {
+    let sink = Sink::default();
+    let mut reader = TerminalReader::generic(
+        Chunks::new(&[b"ab", b"cd"]).then_fail(io::ErrorKind::ConnectionReset),
+    )
+    .debug_log_input(sink.clone());
+    let mut buf = Vec::new();
+    assert!(reader.read_to_end(&mut buf).is_err());
+    assert_eq!(sink.contents(), b"abcd");
+
+    let sink = Sink::default();
+    let mut reader =
+        TerminalReader::generic(Chunks::new(&[b"ab"]).then_fail(io::ErrorKind::ConnectionReset))
+            .debug_log_input(sink.clone());
+    let mut buf = [0_u8; 4];
+    assert!(reader.read_exact(&mut buf).is_err());
+    assert_eq!(sink.contents(), b"ab");
+}
+
+#[test]
+fn debug_log_input_read_to_string_error() {
+    let sink = Sink::default();
+    let mut reader = TerminalReader::generic(
+        Chunks::new(&[b"ab", b"\xc3"]).then_fail(io::ErrorKind::ConnectionReset),
+    )
+    .debug_log_input(sink.clone());
+    let mut s = String::new();
+    let err = reader.read_to_string(&mut s).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+    assert_eq!(s, "ab");
+    assert_eq!(sink.contents(), b"ab\xc3");
+}
+
+#[test]
+fn debug_log_input_send_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let reader = TerminalReader::generic(io::empty());
+    assert_send_sync(&reader);
+    let reader = reader.debug_log_input(Sink::default());
+    assert_send_sync(&reader);
+}
+
+/// Read everything from `reader` using individual `read` calls.
+fn read_all(reader: &mut impl Read) -> Vec<u8> {
+    let mut buf = [0_u8; 16];
+    let mut read = Vec::new();
+    loop {
+        let n = reader.read(&mut buf).unwrap();
+        if n == 0 {
+            return read;
+        }
+        read.extend_from_slice(&buf[..n]);
+    }
+}
+
+fn normalizing(chunks: &[&'static [u8]]) -> TerminalReader<Chunks> {
+    TerminalReader::generic(Chunks::new(chunks)).normalize_enter(true)
+}
+
+#[test]
+fn normalize_enter_raw() {
+    assert_eq!(read_all(&mut normalizing(&[b"ab\r", b"cd\r"])), b"ab\ncd\n");
+    assert_eq!(read_all(&mut normalizing(&[b"\r\r"])), b"\n\n");
+}
+
+#[test]
+fn normalize_enter_canonical() {
+    assert_eq!(read_all(&mut normalizing(&[b"ab\n", b"cd\n"])), b"ab\ncd\n");
+    assert_eq!(read_all(&mut normalizing(&[b"ab\r\ncd\r\n"])), b"ab\ncd\n");
+}
+
+#[test]
+fn normalize_enter_split() {
+    assert_eq!(read_all(&mut normalizing(&[b"ab\r", b"\ncd"])), b"ab\ncd");
+    assert_eq!(read_all(&mut normalizing(&[b"ab\r", b"\n"])), b"ab\n");
+    assert_eq!(
+        read_all(&mut normalizing(&[b"ab\r", b"\n", b"cd\r", b"\n"])),
+        b"ab\ncd\n"
+    );
+
+    // Only a `\n` immediately following the `\r` is dropped.
+    assert_eq!(
+        read_all(&mut normalizing(&[b"a\r", b"b", b"\nc"])),
+        b"a\nb\nc"
+    );
+}
+
+// Unbuffered `bytes` performs 1-byte reads, which is what this is testing.
+#[allow(clippy::unbuffered_bytes)]
+#[test]
+fn normalize_enter_bytes() {
+    let bytes = normalizing(&[b"ab\r\ncd\r\n"])
+        .bytes()
+        .collect::<io::Result<Vec<u8>>>()
+        .unwrap();
+    assert_eq!(bytes, b"ab\ncd\n");
+}
+
+#[test]
+fn normalize_enter_disabled() {
+    let mut reader = TerminalReader::generic(Chunks::new(&[b"ab\r", b"\ncd\r"]));
+    assert_eq!(read_all(&mut reader), b"ab\r\ncd\r");
+}
+
+#[test]
+fn normalize_enter_read_exact() {
+    let mut reader = normalizing(&[b"ab\r", b"\ncd"]);
+    let mut buf = [0_u8; 5];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ab\ncd");
+
+    let mut reader = normalizing(&[b"a\r\nb"]);
+    let mut buf = [0_u8; 1];
+    let mut read = Vec::new();
+    for _ in 0..3 {
+        reader.read_exact(&mut buf).unwrap();
+        read.push(buf[0]);
+    }
+    assert_eq!(read, b"a\nb");
+    assert_eq!(
+        reader.read_exact(&mut buf).unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn normalize_enter_read_to_string() {
+    let mut s = String::new();
+    normalizing(&[b"ab\r", b"\ncd\r", b"ef"])
+        .read_to_string(&mut s)
+        .unwrap();
+    assert_eq!(s, "ab\ncd\nef");
+}
+
+#[test]
+fn normalize_enter_read_to_end_error() {
+    let mut reader = TerminalReader::generic(
+        Chunks::new(&[b"a\r", b"\n", b"b\r"]).then_fail(io::ErrorKind::ConnectionReset),
+    )
+    .normalize_enter(true);
+    let mut buf = Vec::new();
+    assert!(reader.read_to_end(&mut buf).is_err());
+    assert_eq!(buf, b"a\nb\n");
+}
@@ -0,0 +1,127 @@
+use terminal_io::{TerminalColorSupport, TerminalWriter, WriteTerminal};
+
+fn terminal() -> TerminalWriter<Vec<u8>> {
+    TerminalWriter::from(Vec::new(), true, TerminalColorSupport::Monochrome, false)
+}
+
+fn non_terminal() -> TerminalWriter<Vec<u8>> {
+    TerminalWriter::from(Vec::new(), false, TerminalColorSupport::Monochrome, false)
+}
+
+#[cfg(not(windows))]
+#[test]
+fn null_sink_dev_null() {
+    let dev_null = std::fs::OpenOptions::new()
+        .write(true)
+        .open("/dev/null")
+        .unwrap();
+    let writer = TerminalWriter::with_handle(dev_null);
+    assert!(writer.is_null_sink());
+    assert!(!writer.is_output_terminal());
+}
+
+#[cfg(not(windows))]
+#[test]
+fn null_sink_regular_file() {
+    let path =
+        std::env::temp_dir().join(format!("terminal-io-null-sink-test-{}", std::process::id()));
+    let file = std::fs::File::create(&path).unwrap();
+    let writer = TerminalWriter::with_handle(file);
+    std::fs::remove_file(&path).unwrap();
+    assert!(!writer.is_null_sink());
+    assert!(!writer.is_output_terminal());
+}
+
+#[cfg(windows)]
+#[test]
+fn null_sink_nul() {
+    let nul = std::fs::OpenOptions::new().write(true).open("NUL").unwrap();
+    let writer = TerminalWriter::with_handle(nul);
+    assert!(writer.is_null_sink());
+    assert!(!writer.is_output_terminal());
+}
+
+#[test]
+fn null_sink_generic() {
+    let writer = TerminalWriter::generic(Vec::new());
+    assert!(!writer.is_null_sink());
+}
+
+#[test]
+fn set_palette_color() {
+    let mut writer = terminal();
+    writer.set_palette_color(1, 0xff, 0x80, 0x0a).unwrap();
+    writer.set_palette_color(255, 0, 1, 2).unwrap();
+    assert_eq!(
+        writer.into_inner(),
+        b"\x1b]4;1;rgb:ff/80/0a\x1b\\\x1b]4;255;rgb:00/01/02\x1b\\"
+    );
+}
+
+#[test]
+fn set_palette_color_ignores_color_support() {
+    // Palette entries beyond the detected color support are still accepted.
+    let mut writer = TerminalWriter::from(Vec::new(), true, TerminalColorSupport::Classic8, true);
+    writer.set_palette_color(200, 1, 2, 3).unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]4;200;rgb:01/02/03\x1b\\");
+}
+
+#[test]
+fn reset_palette() {
+    let mut writer = terminal();
+    writer.reset_palette().unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]104\x1b\\");
+}
+
+#[test]
+fn palette_non_terminal() {
+    let mut writer = non_terminal();
+    writer.set_palette_color(1, 2, 3, 4).unwrap();
+    writer.reset_palette().unwrap();
+    assert!(writer.into_inner().is_empty());
+}
+
+#[test]
+fn mark_prompt_and_command() {
+    let mut writer = terminal();
+    writer.mark_prompt_start().unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]133;A\x1b\\");
+
+    let mut writer = terminal();
+    writer.mark_prompt_end().unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]133;B\x1b\\");
+
+    let mut writer = terminal();
+    writer.mark_command_start().unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]133;C\x1b\\");
+}
+
+#[test]
+fn mark_command_end() {
+    let mut writer = terminal();
+    writer.mark_command_end(None).unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]133;D\x1b\\");
+
+    let mut writer = terminal();
+    writer.mark_command_end(Some(0)).unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]133;D;0\x1b\\");
+
+    let mut writer = terminal();
+    writer.mark_command_end(Some(127)).unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]133;D;127\x1b\\");
+
+    let mut writer = terminal();
+    writer.mark_command_end(Some(-1)).unwrap();
+    assert_eq!(writer.into_inner(), b"\x1b]133;D;-1\x1b\\");
+}
+
+#[test]
+fn mark_non_terminal() {
+    let mut writer = non_terminal();
+    writer.mark_prompt_start().unwrap();
+    writer.mark_prompt_end().unwrap();
+    writer.mark_command_start().unwrap();
+    writer.mark_command_end(Some(1)).unwrap();
+    writer.mark_command_end(None).unwrap();
+    assert!(writer.into_inner().is_empty());
+}